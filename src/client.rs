@@ -0,0 +1,102 @@
+//! WebFinger resolution client, enabled by the `client` crate feature.
+//!
+//! Several downstream consumers of this crate (fedimovies, goldfinger, webfinger-rs, ...) wrap
+//! [JsonResourceDescriptor] in the same fetch-and-parse routine: given `acct:user@host`, derive
+//! `https://host/.well-known/webfinger?resource=...`, issue a GET with `Accept: application/jrd+json`,
+//! and parse the body. This module does that once so callers don't have to re-implement it.
+
+use crate::JsonResourceDescriptor;
+
+/// The media type a WebFinger endpoint is expected to respond with, per RFC 7033.
+pub const JRD_CONTENT_TYPE: &str = "application/jrd+json";
+
+/// Error produced while resolving a WebFinger resource.
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+	#[error("resource '{0}' has no host part to resolve against")]
+	InvalidResource(String),
+	#[error("invalid url: {0}")]
+	Url(#[from] url::ParseError),
+	#[error("http error: {0}")]
+	Http(#[from] reqwest::Error),
+}
+
+/// Resolves `resource` (e.g. `acct:user@host`) against its host's `/.well-known/webfinger` endpoint,
+/// using a default [Resolver].
+///
+/// This derives `https://host/.well-known/webfinger?resource=...`, issues a GET with
+/// `Accept: application/jrd+json`, and parses the body into a [JsonResourceDescriptor]. Use
+/// [Resolver] directly to set `rel` filters or a custom HTTP client.
+pub async fn resolve(resource: &str) -> Result<JsonResourceDescriptor, ClientError> {
+	Resolver::new().resolve(resource).await
+}
+
+/// Builder for a WebFinger resolution request, for callers who need to restrict the `rel` filter or
+/// supply a pre-configured HTTP client (e.g. one routed through a proxy).
+#[derive(Debug, Default, Clone)]
+pub struct Resolver {
+	rels: Vec<String>,
+	client: Option<reqwest::Client>,
+}
+
+impl Resolver {
+	/// Creates a resolver with no `rel` filter and a default [reqwest::Client].
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a `rel` value to request via the WebFinger `rel` query parameter.
+	///
+	/// May be called multiple times; each adds another `rel` parameter to the request.
+	pub fn rel(mut self, rel: impl Into<String>) -> Self {
+		self.rels.push(rel.into());
+		self
+	}
+
+	/// Uses `client` instead of a default [reqwest::Client] to perform the request.
+	pub fn client(mut self, client: reqwest::Client) -> Self {
+		self.client = Some(client);
+		self
+	}
+
+	/// Resolves `resource` (a URI resource like `https://example.com/` or an `acct:user@host` one)
+	/// against its host's `/.well-known/webfinger` endpoint.
+	pub async fn resolve(self, resource: &str) -> Result<JsonResourceDescriptor, ClientError> {
+		let host = host_of(resource)?;
+		let mut url = url::Url::parse(&format!("https://{host}/.well-known/webfinger"))?;
+		{
+			let mut query = url.query_pairs_mut();
+			query.append_pair("resource", resource);
+			for rel in &self.rels {
+				query.append_pair("rel", rel);
+			}
+		}
+
+		let client = self.client.unwrap_or_default();
+		let response = client
+			.get(url)
+			.header(reqwest::header::ACCEPT, JRD_CONTENT_TYPE)
+			.send()
+			.await?
+			.error_for_status()?;
+
+		Ok(response.json::<JsonResourceDescriptor>().await?)
+	}
+}
+
+/// Extracts the host to resolve `resource` against.
+///
+/// WebFinger resources aren't only `acct:user@host` URIs; a resource may be any URI, including plain
+/// `https://host/path` ones, which carry their host in the authority rather than after an `@`. This
+/// tries parsing `resource` as a URI first and falls back to the `user@host` form.
+fn host_of(resource: &str) -> Result<String, ClientError> {
+	if let Some(host) = url::Url::parse(resource).ok().and_then(|url| url.host_str().map(str::to_string)) {
+		return Ok(host);
+	}
+
+	resource
+		.rsplit_once('@')
+		.map(|(_, host)| host.to_string())
+		.filter(|host| !host.is_empty())
+		.ok_or_else(|| ClientError::InvalidResource(resource.to_string()))
+}
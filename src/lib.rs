@@ -43,7 +43,7 @@
 //! let jrd_struct = jrd::JsonResourceDescriptor {
 //!   subject: "acct:paulej@packetizer.com".into(),
 //!   aliases: Vec::new(),
-//!   properties: [("http://packetizer.com/ns/name".to_string(), "Paul E. Jones".to_string())].into(),
+//!   properties: [("http://packetizer.com/ns/name".to_string(), Some("Paul E. Jones".to_string()))].into(),
 //!   expires: None,
 //!   links: vec![
 //!     jrd::JsonResourceDescriptorLink {
@@ -51,14 +51,14 @@
 //!       href: Some("http://www.packetizer.com/people/paulej/".into()),
 //!       link_type: None,
 //!       titles: jrd::Map::default(),
-//!       properties: jrd::Map::default(),
+//!       properties: jrd::PropertyMap::default(),
 //!     },
 //!     jrd::JsonResourceDescriptorLink {
 //!       rel: "http://packetizer.com/rel/blog".into(),
 //!       href: Some("http://www.packetizer.com/people/paulej/blog/".into()),
 //!       link_type: Some("text/html".into()),
 //!       titles: [("en-us".to_string(), "Paul E. Jones' Blog".to_string())].into(),
-//!       properties: jrd::Map::default(),
+//!       properties: jrd::PropertyMap::default(),
 //!     },
 //!   ],
 //! };
@@ -71,7 +71,18 @@
 //! ```
 
 
+#[cfg(feature = "xrd")]
+mod xrd;
+#[cfg(feature = "xrd")]
+pub use xrd::XrdError;
+
+#[cfg(feature = "client")]
+mod client;
+#[cfg(feature = "client")]
+pub use client::{resolve, ClientError, Resolver, JRD_CONTENT_TYPE};
+
 pub type Map = std::collections::BTreeMap<String, String>;
+pub type PropertyMap = std::collections::BTreeMap<String, Option<String>>;
 pub type Time = chrono::DateTime<chrono::Utc>;
 
 
@@ -133,9 +144,9 @@ pub struct JsonResourceDescriptor {
 	///  ```json
 	///      "properties" : { "http://packetizer.com/ns/name" : "Bob Smith" }
 	///  ```
-	/// The “properties” member is optional. 
-	#[serde(default, skip_serializing_if = "Map::is_empty")]
-	pub properties: Map,
+	/// The “properties” member is optional.
+	#[serde(default, skip_serializing_if = "PropertyMap::is_empty")]
+	pub properties: PropertyMap,
 
 	/// The value of the “expires” member is a string that indicates the date and time after which the JRD SHOULD be considered expired and no longer utilized.
 	///
@@ -221,7 +232,201 @@ pub struct JsonResourceDescriptorLink {
 	/// ```json
 	///   "properties" : { "http://packetizer.com/ns/port" : "993" }
 	/// ```
-	/// The “properties” member is optional in a link relation object. 
-	#[serde(default, skip_serializing_if = "Map::is_empty")]
-	pub properties: Map,
+	/// The “properties” member is optional in a link relation object.
+	#[serde(default, skip_serializing_if = "PropertyMap::is_empty")]
+	pub properties: PropertyMap,
+}
+
+impl JsonResourceDescriptorLink {
+	/// Returns the value of the property identified by `key`, flattening the absent-vs-null distinction.
+	///
+	/// `None` is returned both when the property is not present and when it is present with a `null` value;
+	/// callers who need to tell these apart should look up `key` in [JsonResourceDescriptorLink::properties] directly.
+	pub fn property(&self, key: &str) -> Option<&str> {
+		self.properties.get(key)?.as_deref()
+	}
+}
+
+impl JsonResourceDescriptor {
+	/// Returns an iterator over all [JsonResourceDescriptorLink] entries whose `rel` matches `rel`.
+	///
+	/// Comparison follows the "Simple String Comparison" algorithm of Section 6.2.1 of
+	/// [RFC 3986](https://www.packetizer.com/rfc/rfc3986/), i.e. an exact byte-for-byte match.
+	pub fn links_with_rel<'a>(&'a self, rel: &str) -> impl Iterator<Item = &'a JsonResourceDescriptorLink> {
+		let rel = rel.to_string();
+		self.links.iter().filter(move |link| link.rel == rel)
+	}
+
+	/// Returns the first [JsonResourceDescriptorLink] whose `rel` matches `rel`, if any.
+	///
+	/// As the [JsonResourceDescriptorLink] docs note, when multiple link relations share the same `rel` the first
+	/// one in the array indicates the preferred link.
+	pub fn first_link(&self, rel: &str) -> Option<&JsonResourceDescriptorLink> {
+		self.links_with_rel(rel).next()
+	}
+
+	/// Returns the value of the property identified by `key`, flattening the absent-vs-null distinction.
+	///
+	/// `None` is returned both when the property is not present and when it is present with a `null` value;
+	/// callers who need to tell these apart should look up `key` in [JsonResourceDescriptor::properties] directly.
+	pub fn property(&self, key: &str) -> Option<&str> {
+		self.properties.get(key)?.as_deref()
+	}
+
+	/// Returns a clone of this JRD keeping only the links whose `rel` is one of `rels`.
+	///
+	/// This implements WebFinger's `rel` query parameter filtering, which asks servers to return only
+	/// the requested link relations, without requiring callers to hand-roll the iteration themselves.
+	pub fn filter_rels(&self, rels: &[&str]) -> JsonResourceDescriptor {
+		JsonResourceDescriptor {
+			links: self.links.iter()
+				.filter(|link| rels.contains(&link.rel.as_str()))
+				.cloned()
+				.collect(),
+			..self.clone()
+		}
+	}
+
+	/// Returns the `rel == "self"` link, if any.
+	///
+	/// ActivityPub actors advertise themselves via a `self` link relation whose `type` is one of the
+	/// ActivityPub media types; see [JsonResourceDescriptor::actor_url] to resolve that URL directly.
+	pub fn self_link(&self) -> Option<&JsonResourceDescriptorLink> {
+		self.first_link("self")
+	}
+
+	/// Returns the `href` of the first `self` link whose `type` matches one of `preferred_types`,
+	/// in the given preference order.
+	///
+	/// This only ranks candidates by their `type` (e.g. preferring `application/ld+json; profile="..."`
+	/// over the plainer `application/activity+json`); it cannot pick between actors that share the same
+	/// media type (e.g. a Lemmy `Group` vs. `Person` `self` link, both `application/activity+json`) since
+	/// the JRD alone carries no signal to distinguish them. Callers facing that case need to dereference
+	/// each candidate `href` and inspect the fetched actor.
+	pub fn actor_url(&self, preferred_types: &[&str]) -> Option<&str> {
+		preferred_types.iter().find_map(|preferred| {
+			self.links_with_rel("self")
+				.find(|link| link.link_type.as_deref() == Some(*preferred))
+				.and_then(|link| link.href.as_deref())
+		})
+	}
+
+	/// Returns whether this JRD's `expires` has passed as of `now`.
+	///
+	/// Returns `false` when `expires` is `None`, per the “expires” field docs: it is OPTIONAL and,
+	/// when absent, there is nothing to consider expired.
+	pub fn is_expired(&self, now: Time) -> bool {
+		self.expires.is_some_and(|expires| expires <= now)
+	}
+
+	/// Strips `expires` before emitting, enforcing the WebFinger profile (RFC 7033), which requires
+	/// the “expires” member to not be transmitted, in contrast to the RFC 6415 host-metadata default
+	/// where it's kept.
+	pub fn into_webfinger(mut self) -> Self {
+		self.expires = None;
+		self
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn link(rel: &str) -> JsonResourceDescriptorLink {
+		JsonResourceDescriptorLink { rel: rel.to_string(), ..Default::default() }
+	}
+
+	#[test]
+	fn links_with_rel_matches_exact_rel_only() {
+		let jrd = JsonResourceDescriptor {
+			links: vec![link("self"), link("http://webfinger.net/rel/profile-page"), link("self")],
+			..Default::default()
+		};
+
+		assert_eq!(jrd.links_with_rel("self").count(), 2);
+		assert_eq!(jrd.links_with_rel("missing").count(), 0);
+	}
+
+	#[test]
+	fn first_link_returns_the_first_match() {
+		let jrd = JsonResourceDescriptor {
+			links: vec![
+				JsonResourceDescriptorLink { href: Some("a".into()), ..link("self") },
+				JsonResourceDescriptorLink { href: Some("b".into()), ..link("self") },
+			],
+			..Default::default()
+		};
+
+		assert_eq!(jrd.first_link("self").and_then(|l| l.href.as_deref()), Some("a"));
+		assert_eq!(jrd.first_link("missing"), None);
+	}
+
+	#[test]
+	fn filter_rels_keeps_only_matching_links_and_other_fields() {
+		let jrd = JsonResourceDescriptor {
+			subject: "acct:bob@example.com".into(),
+			links: vec![link("self"), link("http://webfinger.net/rel/profile-page"), link("other")],
+			..Default::default()
+		};
+
+		let filtered = jrd.filter_rels(&["self", "other"]);
+
+		assert_eq!(filtered.subject, jrd.subject);
+		assert_eq!(filtered.links.iter().map(|l| l.rel.as_str()).collect::<Vec<_>>(), vec!["self", "other"]);
+	}
+
+	#[test]
+	fn self_link_finds_the_self_rel() {
+		let jrd = JsonResourceDescriptor {
+			links: vec![link("http://webfinger.net/rel/profile-page"), link("self")],
+			..Default::default()
+		};
+
+		assert_eq!(jrd.self_link().map(|l| l.rel.as_str()), Some("self"));
+	}
+
+	#[test]
+	fn actor_url_prefers_earlier_types_over_later_ones() {
+		let activity_json = JsonResourceDescriptorLink {
+			href: Some("https://example.com/actor".into()),
+			link_type: Some("application/activity+json".into()),
+			..link("self")
+		};
+		let ld_json = JsonResourceDescriptorLink {
+			href: Some("https://example.com/actor.jsonld".into()),
+			link_type: Some(r#"application/ld+json; profile="https://www.w3.org/ns/activitystreams""#.into()),
+			..link("self")
+		};
+		let jrd = JsonResourceDescriptor { links: vec![activity_json, ld_json], ..Default::default() };
+
+		let preferred_types = ["application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"", "application/activity+json"];
+		assert_eq!(jrd.actor_url(&preferred_types), Some("https://example.com/actor.jsonld"));
+
+		assert_eq!(jrd.actor_url(&["text/html"]), None);
+	}
+
+	#[test]
+	fn is_expired_is_false_without_an_expires_field() {
+		let jrd = JsonResourceDescriptor::default();
+		assert!(!jrd.is_expired(Time::default()));
+	}
+
+	#[test]
+	fn is_expired_compares_against_now() {
+		let past: Time = "2000-01-01T00:00:00Z".parse().unwrap();
+		let future: Time = "2100-01-01T00:00:00Z".parse().unwrap();
+		let now: Time = "2050-01-01T00:00:00Z".parse().unwrap();
+
+		let jrd = JsonResourceDescriptor { expires: Some(past), ..Default::default() };
+		assert!(jrd.is_expired(now));
+
+		let jrd = JsonResourceDescriptor { expires: Some(future), ..Default::default() };
+		assert!(!jrd.is_expired(now));
+	}
+
+	#[test]
+	fn into_webfinger_strips_expires() {
+		let jrd = JsonResourceDescriptor { expires: Some(Time::default()), ..Default::default() };
+		assert_eq!(jrd.into_webfinger().expires, None);
+	}
 }
@@ -0,0 +1,308 @@
+//! XRD XML (de)serialization, enabled by the `xrd` crate feature.
+//!
+//! JRD is a JSON restatement of the older Extensible Resource Descriptor (XRD) format, and some
+//! WebFinger clients and host-meta consumers still negotiate `application/xrd+xml` instead of
+//! `application/jrd+json`. This module converts a [JsonResourceDescriptor] to and from that XML
+//! representation (`<XRD>` root with `<Subject>`, `<Alias>`, `<Property type="...">`,
+//! `<Link rel=... type=... href=...>` with nested `<Title xml:lang=...>`, and `<Expires>`), so a
+//! single in-memory model can serve or consume both formats.
+
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::io::Cursor;
+
+use crate::{JsonResourceDescriptor, JsonResourceDescriptorLink};
+
+const XRD_NS: &str = "http://docs.oasis-open.org/ns/xri/xrd-1.0";
+
+/// Error produced while converting to or from the XRD XML representation.
+#[derive(Debug, thiserror::Error)]
+pub enum XrdError {
+	#[error("xml error: {0}")]
+	Xml(#[from] quick_xml::Error),
+	#[error("xml attribute error: {0}")]
+	Attribute(#[from] quick_xml::events::attributes::AttrError),
+	#[error("invalid timestamp in <Expires>: {0}")]
+	Time(#[from] chrono::ParseError),
+}
+
+/// Local names of the XRD elements this module understands, whether directly under `<XRD>` or nested in `<Link>`.
+enum Element {
+	Subject,
+	Alias,
+	Property,
+	Expires,
+	Link,
+	Title,
+	Other,
+}
+
+impl Element {
+	fn from_local_name(name: &[u8]) -> Element {
+		match name {
+			b"Subject" => Element::Subject,
+			b"Alias" => Element::Alias,
+			b"Property" => Element::Property,
+			b"Expires" => Element::Expires,
+			b"Link" => Element::Link,
+			b"Title" => Element::Title,
+			_ => Element::Other,
+		}
+	}
+}
+
+impl JsonResourceDescriptor {
+	/// Parses an XRD XML document (as served by e.g. `/.well-known/host-meta`) into a [JsonResourceDescriptor].
+	pub fn from_xrd_str(xml: &str) -> Result<JsonResourceDescriptor, XrdError> {
+		let mut reader = Reader::from_str(xml);
+		reader.config_mut().trim_text(true);
+
+		let mut jrd = JsonResourceDescriptor::default();
+		let mut link: Option<JsonResourceDescriptorLink> = None;
+		let mut property_key: Option<String> = None;
+		let mut title_lang: Option<String> = None;
+		let mut current = Element::Other;
+		let mut buf = Vec::new();
+
+		loop {
+			match reader.read_event_into(&mut buf)? {
+				Event::Eof => break,
+				Event::Start(e) => {
+					current = Element::from_local_name(e.local_name().as_ref());
+					match current {
+						Element::Link => {
+							let mut l = JsonResourceDescriptorLink::default();
+							for attr in e.attributes() {
+								let attr = attr?;
+								let value = attr.decode_and_unescape_value(reader.decoder())?.into_owned();
+								match attr.key.as_ref() {
+									b"rel" => l.rel = value,
+									b"type" => l.link_type = Some(value),
+									b"href" => l.href = Some(value),
+									_ => {}
+								}
+							}
+							link = Some(l);
+						}
+						Element::Property => {
+							property_key = find_attr(&e, &reader, b"type")?;
+						}
+						Element::Title => {
+							title_lang = find_attr(&e, &reader, b"xml:lang")?
+								.or_else(|| Some("und".to_string()));
+						}
+						_ => {}
+					}
+				}
+				Event::Empty(e) => {
+					// self-closed elements never carry text, so handle them eagerly
+					match Element::from_local_name(e.local_name().as_ref()) {
+						Element::Link => {
+							let mut l = JsonResourceDescriptorLink::default();
+							for attr in e.attributes() {
+								let attr = attr?;
+								let value = attr.decode_and_unescape_value(reader.decoder())?.into_owned();
+								match attr.key.as_ref() {
+									b"rel" => l.rel = value,
+									b"type" => l.link_type = Some(value),
+									b"href" => l.href = Some(value),
+									_ => {}
+								}
+							}
+							jrd.links.push(l);
+						}
+						Element::Property => {
+							// a self-closed `<Property type="..."/>` is how `to_xrd_string` writes a null value
+							if let Some(key) = find_attr(&e, &reader, b"type")? {
+								if let Some(l) = link.as_mut() {
+									l.properties.insert(key, None);
+								} else {
+									jrd.properties.insert(key, None);
+								}
+							}
+						}
+						_ => {}
+					}
+				}
+				Event::Text(t) => {
+					let text = t.unescape()?.into_owned();
+					match current {
+						Element::Subject => jrd.subject = text,
+						Element::Alias => jrd.aliases.push(text),
+						Element::Expires => jrd.expires = Some(text.parse::<crate::Time>()?),
+						Element::Property => {
+							if let Some(key) = property_key.take() {
+								if let Some(l) = link.as_mut() {
+									l.properties.insert(key, Some(text));
+								} else {
+									jrd.properties.insert(key, Some(text));
+								}
+							}
+						}
+						Element::Title => {
+							if let (Some(l), Some(lang)) = (link.as_mut(), title_lang.take()) {
+								l.titles.insert(lang, text);
+							}
+						}
+						_ => {}
+					}
+				}
+				Event::End(e) => {
+					match Element::from_local_name(e.local_name().as_ref()) {
+						Element::Link => {
+							if let Some(l) = link.take() {
+								jrd.links.push(l);
+							}
+						}
+						Element::Property => {
+							// a null property has no text child, so it's only inserted here
+							if let Some(key) = property_key.take() {
+								if let Some(l) = link.as_mut() {
+									l.properties.entry(key).or_insert(None);
+								} else {
+									jrd.properties.entry(key).or_insert(None);
+								}
+							}
+						}
+						_ => {}
+					}
+					current = Element::Other;
+				}
+				_ => {}
+			}
+			buf.clear();
+		}
+
+		Ok(jrd)
+	}
+
+	/// Serializes this JRD as an XRD XML document, mirroring the fields understood by [JsonResourceDescriptor::from_xrd_str].
+	pub fn to_xrd_string(&self) -> Result<String, XrdError> {
+		let mut writer = Writer::new(Cursor::new(Vec::new()));
+
+		let mut xrd = BytesStart::new("XRD");
+		xrd.push_attribute(("xmlns", XRD_NS));
+		writer.write_event(Event::Start(xrd))?;
+
+		if !self.subject.is_empty() {
+			write_text_element(&mut writer, "Subject", &self.subject)?;
+		}
+		for alias in &self.aliases {
+			write_text_element(&mut writer, "Alias", alias)?;
+		}
+		for (key, value) in &self.properties {
+			write_property(&mut writer, key, value.as_deref())?;
+		}
+		if let Some(expires) = &self.expires {
+			write_text_element(&mut writer, "Expires", &expires.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))?;
+		}
+		for link in &self.links {
+			write_link(&mut writer, link)?;
+		}
+
+		writer.write_event(Event::End(quick_xml::events::BytesEnd::new("XRD")))?;
+
+		Ok(String::from_utf8_lossy(&writer.into_inner().into_inner()).into_owned())
+	}
+}
+
+fn find_attr(e: &BytesStart, reader: &Reader<&[u8]>, key: &[u8]) -> Result<Option<String>, XrdError> {
+	for attr in e.attributes() {
+		let attr = attr?;
+		if attr.key.as_ref() == key || attr.key.local_name().as_ref() == key {
+			return Ok(Some(attr.decode_and_unescape_value(reader.decoder())?.into_owned()));
+		}
+	}
+	Ok(None)
+}
+
+fn write_text_element(writer: &mut Writer<Cursor<Vec<u8>>>, name: &str, text: &str) -> Result<(), XrdError> {
+	writer.write_event(Event::Start(BytesStart::new(name)))?;
+	writer.write_event(Event::Text(BytesText::new(text)))?;
+	writer.write_event(Event::End(quick_xml::events::BytesEnd::new(name)))?;
+	Ok(())
+}
+
+fn write_property(writer: &mut Writer<Cursor<Vec<u8>>>, key: &str, value: Option<&str>) -> Result<(), XrdError> {
+	let mut start = BytesStart::new("Property");
+	start.push_attribute(("type", key));
+	match value {
+		Some(text) => {
+			writer.write_event(Event::Start(start))?;
+			writer.write_event(Event::Text(BytesText::new(text)))?;
+			writer.write_event(Event::End(quick_xml::events::BytesEnd::new("Property")))?;
+		}
+		None => writer.write_event(Event::Empty(start))?,
+	}
+	Ok(())
+}
+
+fn write_link(writer: &mut Writer<Cursor<Vec<u8>>>, link: &JsonResourceDescriptorLink) -> Result<(), XrdError> {
+	let mut start = BytesStart::new("Link");
+	start.push_attribute(("rel", link.rel.as_str()));
+	if let Some(link_type) = &link.link_type {
+		start.push_attribute(("type", link_type.as_str()));
+	}
+	if let Some(href) = &link.href {
+		start.push_attribute(("href", href.as_str()));
+	}
+
+	if link.titles.is_empty() && link.properties.is_empty() {
+		writer.write_event(Event::Empty(start))?;
+		return Ok(());
+	}
+
+	writer.write_event(Event::Start(start))?;
+	for (lang, title) in &link.titles {
+		let mut el = BytesStart::new("Title");
+		el.push_attribute(("xml:lang", lang.as_str()));
+		writer.write_event(Event::Start(el))?;
+		writer.write_event(Event::Text(BytesText::new(title)))?;
+		writer.write_event(Event::End(quick_xml::events::BytesEnd::new("Title")))?;
+	}
+	for (key, value) in &link.properties {
+		write_property(writer, key, value.as_deref())?;
+	}
+	writer.write_event(Event::End(quick_xml::events::BytesEnd::new("Link")))?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{JsonResourceDescriptor, JsonResourceDescriptorLink};
+
+	#[test]
+	fn round_trips_through_xrd() {
+		let jrd = JsonResourceDescriptor {
+			subject: "acct:paulej@packetizer.com".into(),
+			aliases: vec!["https://www.packetizer.com/paulej/".into()],
+			properties: [
+				("http://packetizer.com/ns/name".to_string(), Some("Paul E. Jones".to_string())),
+				("http://packetizer.com/ns/port".to_string(), None),
+			].into(),
+			expires: None,
+			links: vec![
+				JsonResourceDescriptorLink {
+					rel: "http://packetizer.com/rel/blog".into(),
+					href: Some("http://www.packetizer.com/people/paulej/blog/".into()),
+					link_type: Some("text/html".into()),
+					titles: [("en-us".to_string(), "Paul E. Jones' Blog".to_string())].into(),
+					properties: [("http://packetizer.com/ns/port".to_string(), None)].into(),
+				},
+			],
+		};
+
+		let xml = jrd.to_xrd_string().expect("serialize to xrd");
+		let parsed = JsonResourceDescriptor::from_xrd_str(&xml).expect("parse xrd");
+
+		assert_eq!(parsed, jrd);
+	}
+
+	#[test]
+	fn parses_null_property() {
+		let xml = r#"<XRD xmlns="http://docs.oasis-open.org/ns/xri/xrd-1.0"><Property type="http://packetizer.com/ns/port"/></XRD>"#;
+		let jrd = JsonResourceDescriptor::from_xrd_str(xml).expect("parse xrd");
+		assert_eq!(jrd.properties.get("http://packetizer.com/ns/port"), Some(&None));
+	}
+}